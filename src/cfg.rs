@@ -6,6 +6,14 @@ use serde::Deserialize;
 pub struct Config {
     pub input: Input,
     pub output: Output,
+
+    #[serde(default)]
+    pub tuning: Tuning,
+
+    /// If present, render the configured patch across a range of MIDI notes
+    /// and export a multisampled SFZ instrument.
+    #[serde(default)]
+    pub multisample: Option<Multisample>,
 }
 
 impl Config {
@@ -16,12 +24,93 @@ impl Config {
                 if stdev <= 0.0 {
                     bail!("invalid config file: output mode Harmonic stdev must be greater than 0, is {}", stdev);
                 }
+            }
+            SynthMode::PreserveSpectrum { stdev, .. } => {
+                if stdev <= 0.0 {
+                    bail!("invalid config file: output mode PreserveSpectrum stdev must be greater than 0, is {}", stdev);
+                }
+            }
+            SynthMode::PreserveFormants { stdev, lifter_order, .. } => {
+                if stdev <= 0.0 {
+                    bail!("invalid config file: output mode PreserveFormants stdev must be greater than 0, is {}", stdev);
+                }
+                if lifter_order == 0 {
+                    bail!("invalid config file: output mode PreserveFormants lifter_order must be greater than 0");
+                }
             } // _ => {}
         }
+        if let Some(multisample) = &self.multisample {
+            if multisample.low > multisample.high {
+                bail!(
+                    "invalid config file: multisample low ({}) must be <= high ({})",
+                    multisample.low,
+                    multisample.high
+                );
+            }
+            if multisample.step <= 0 {
+                bail!("invalid config file: multisample step must be greater than 0, is {}", multisample.step);
+            }
+        }
+        match &self.tuning.system {
+            TuningSystem::EqualTemperament {
+                divisions_per_octave,
+                ..
+            } => {
+                if *divisions_per_octave == 0 {
+                    bail!("invalid config file: tuning divisions_per_octave must be greater than 0");
+                }
+            }
+            TuningSystem::Scala { cents_per_step, .. } => {
+                if cents_per_step.is_empty() {
+                    bail!("invalid config file: tuning Scala cents_per_step must not be empty");
+                }
+            }
+        }
         Ok(())
     }
 }
 
+/// Describes how `Pitch::Degree`, `ChordPitch::Midi`/`Degree`, and `midi_to_freq`
+/// resolve scale degrees to frequencies, relative to a single reference pitch.
+#[derive(Deserialize, Debug, Clone)]
+pub struct Tuning {
+    /// The degree (in whatever system below) that `reference_freq` is pinned to.
+    /// For standard 12-TET, this is MIDI note 69 (A4).
+    pub reference_degree: i32,
+    pub reference_freq: f32,
+
+    pub system: TuningSystem,
+}
+
+impl Default for Tuning {
+    /// Standard 12-tone equal temperament at A440, matching the crate's prior hardcoded behavior.
+    fn default() -> Self {
+        Tuning {
+            reference_degree: 69,
+            reference_freq: 440.0,
+            system: TuningSystem::EqualTemperament {
+                divisions_per_octave: 12,
+            },
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub enum TuningSystem {
+    /// `degree` resolves to `reference_freq * 2^((degree - reference_degree) / divisions_per_octave)`.
+    EqualTemperament { divisions_per_octave: u32 },
+
+    /// Scala-style scale: `cents_per_step` lists the `n` ascending steps above the tonic
+    /// (`reference_degree`, implicitly 0 cents), with `cents_per_step[n - 1] == period_cents`
+    /// closing the repeating period (e.g. octave). With `d = degree - reference_degree`,
+    /// `degree` resolves to step `(d - 1) % n` of the `(d - 1).div_euclid(n)`-th period
+    /// above/below the tonic.
+    Scala {
+        cents_per_step: Vec<f32>,
+        period_cents: f32,
+    },
+}
+
 #[derive(Deserialize, Debug)]
 pub struct Input {
     /// TODO If omitted, smpl chunk must be present, and will be used for loop begin.
@@ -36,6 +125,26 @@ pub struct Input {
 
     /// Used to split the input signal up into bins.
     pub pitch: Pitch,
+
+    /// If present, estimate the input power spectrum via Welch's method
+    /// (averaged, windowed, overlapping segments) instead of a single FFT over the whole loop.
+    #[serde(default)]
+    pub welch: Option<Welch>,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy)]
+pub struct Welch {
+    pub segment_len: usize,
+
+    /// Fraction of each segment that overlaps with the next, in `[0, 1)`.
+    #[serde(default = "Welch::default_overlap")]
+    pub overlap: f32,
+}
+
+impl Welch {
+    fn default_overlap() -> f32 {
+        0.5
+    }
 }
 
 #[derive(Deserialize, Debug, Default)]
@@ -51,6 +160,12 @@ pub struct Transpose {
 pub enum Pitch {
     Hz(f32),
     Midi(i32),
+
+    /// A scale degree in the configured `Tuning`, e.g. a step of 19-EDO or a Scala scale.
+    Degree(i32),
+
+    /// Detect the fundamental of `data[loop_begin..loop_end]` via NSDF autocorrelation.
+    Auto,
 }
 
 #[derive(Deserialize, Debug)]
@@ -69,11 +184,68 @@ pub struct Output {
     pub seed: u64,
 }
 
+/// Renders the configured patch once per zone root, exported as an SFZ instrument.
+/// The patch is assumed to be voiced (per `Output::chord`) at `root_note`;
+/// each zone transposes it to its own root.
+#[derive(Deserialize, Debug, Clone, Copy)]
+pub struct Multisample {
+    pub low: i32,
+    pub high: i32,
+
+    /// Spacing between zone roots, in semitones (e.g. 3 = one zone every minor third).
+    #[serde(default = "Multisample::default_step")]
+    pub step: i32,
+
+    /// The MIDI note `Output::chord` is voiced at; zones transpose relative to this.
+    #[serde(default = "Multisample::default_root_note")]
+    pub root_note: i32,
+}
+
+impl Multisample {
+    fn default_step() -> i32 {
+        3
+    }
+    fn default_root_note() -> i32 {
+        60
+    }
+}
+
 #[derive(Deserialize, Debug, Clone, Copy)]
 pub enum SynthMode {
-    // TODO PreserveSpectrum,
-    Harmonic { stdev: f32 },
-    // TODO PreserveFormants { stdev: f32, fund_pitch: Pitch },
+    Harmonic {
+        stdev: f32,
+    },
+
+    /// Resamples the continuous input magnitude spectrum at each output harmonic frequency.
+    /// Preserves the input timbre under large transposition.
+    PreserveSpectrum {
+        stdev: f32,
+        interpolation: InterpolationMode,
+    },
+
+    /// Keeps the input's formants fixed in absolute Hz under transposition. `fund_pitch` is
+    /// the excitation fundamental whose harmonic series is passed through the input's
+    /// spectral envelope; each chord note picks the pitch it's voiced at, as in the other modes.
+    PreserveFormants {
+        stdev: f32,
+        fund_pitch: Pitch,
+
+        /// Quefrency cutoff (in cepstrum bins) of the liftering window used to smooth
+        /// the spectral envelope. Typically ~20-60: too low blurs out real formants,
+        /// too high lets individual harmonic peaks leak into the envelope.
+        lifter_order: usize,
+    },
+}
+
+/// How `SynthMode::PreserveSpectrum` resamples the input magnitude spectrum
+/// between its discrete FFT bins.
+#[derive(Deserialize, Debug, Clone, Copy)]
+pub enum InterpolationMode {
+    Nearest,
+    Linear,
+    Cosine,
+    Cubic,
+    Polyphase,
 }
 
 #[derive(Deserialize, Debug, Clone, Copy)]
@@ -90,9 +262,18 @@ pub struct ChordNote {
 
 #[derive(Deserialize, Debug, Clone, Copy)]
 pub enum ChordPitch {
-    // TODO Harmonic(f32), (only valid if harmonic_stdev is Some)
     Hz(f32),
     Midi(i32),
+
+    /// A scale degree in the configured `Tuning`.
+    Degree(i32),
+
+    /// Cents relative to the input fundamental (`cfg::Input::pitch`, e.g. auto-detected).
+    Cents(f32),
+
+    /// The `h`-th harmonic (possibly fractional, for inharmonic partials) of
+    /// `SynthMode::PreserveFormants::fund_pitch`. Only valid in that mode.
+    Harmonic(f32),
 }
 
 #[derive(Deserialize, Debug, Clone, Copy)]
@@ -37,22 +37,174 @@ fn cents_to_freq_mul(cents: f32) -> f32 {
     2.0f32.powf(cents / 1200.0)
 }
 
-fn midi_to_freq(midi: i32) -> f32 {
-    440.0 * 2.0f32.powf((midi - 69) as f32 / 12.0)
+/// Resolves a scale degree to a frequency via the configured `Tuning`.
+pub(crate) fn tuning_to_freq(tuning: &cfg::Tuning, degree: i32) -> f32 {
+    match &tuning.system {
+        cfg::TuningSystem::EqualTemperament { divisions_per_octave } => {
+            let steps_above_ref = (degree - tuning.reference_degree) as f32;
+            tuning.reference_freq * 2.0f32.powf(steps_above_ref / *divisions_per_octave as f32)
+        }
+        cfg::TuningSystem::Scala {
+            cents_per_step,
+            period_cents,
+        } => {
+            let n = cents_per_step.len() as i32;
+            let d = degree - tuning.reference_degree;
+            // `cents_per_step` lists the `n` ascending steps *above* the (implicit, 0-cent)
+            // tonic, with `cents_per_step[n - 1] == period_cents` closing the period. So
+            // degree `d` indexes step `d - 1` (degree 0 is the tonic itself, at 0 cents,
+            // one step *below* `cents_per_step[0]`); shifting by one before splitting into
+            // periods/step keeps `d == 0` at exactly 0 cents with no double-counting at the
+            // period boundary.
+            let e = d - 1;
+            let periods = e.div_euclid(n);
+            let step = e.rem_euclid(n) as usize;
+            let cents = periods as f32 * period_cents + cents_per_step[step];
+            tuning.reference_freq * cents_to_freq_mul(cents)
+        }
+    }
+}
+
+/// `Pitch::Midi`/`ChordPitch::Midi` route through the configured tuning,
+/// treating the MIDI note number as a scale degree (so the default 12-TET/A440
+/// tuning reproduces the crate's old hardcoded `midi_to_freq` exactly).
+pub(crate) fn midi_to_freq(tuning: &cfg::Tuning, midi: i32) -> f32 {
+    tuning_to_freq(tuning, midi)
+}
+
+/// Frequency search range for `Pitch::Auto`, chosen to reject DC drift and octave errors.
+const AUTO_PITCH_MIN_HZ: f32 = 40.0;
+const AUTO_PITCH_MAX_HZ: f32 = 2000.0;
+
+/// Fraction of the NSDF's highest peak a candidate peak must reach to be accepted.
+/// Taking the *first* peak past this threshold (rather than the global max)
+/// favors the fundamental over louder high harmonics, per the McLeod pitch method.
+const NSDF_PEAK_THRESHOLD: f32 = 0.9;
+
+/// Detects the fundamental frequency of `data` using the McLeod Pitch Method:
+/// normalized square difference function (NSDF) autocorrelation,
+/// `n(tau) = 2 * r(tau) / m(tau)` with `r(tau) = sum x[j] * x[j+tau]`
+/// and `m(tau) = sum x[j]^2 + x[j+tau]^2` over the overlapping window,
+/// followed by parabolic interpolation around the chosen peak.
+fn detect_pitch_nsdf(data: &[Amplitude], smp_per_s: f32, min_freq: f32, max_freq: f32) -> Result<f32> {
+    let min_tau = ((smp_per_s / max_freq).floor() as usize).max(1);
+    let max_tau = ((smp_per_s / min_freq).ceil() as usize).min(data.len().saturating_sub(1));
+    if min_tau > max_tau {
+        bail!(
+            "loop of {} samples is too short to search the configured Pitch::Auto range of {}..={} Hz \
+             (need at least {} samples at {} samples/s)",
+            data.len(),
+            min_freq,
+            max_freq,
+            min_tau + 1,
+            smp_per_s,
+        );
+    }
+
+    let nsdf = |tau: usize| -> f32 {
+        let mut r = 0.0;
+        let mut m = 0.0;
+        for j in 0..data.len() - tau {
+            r += data[j] * data[j + tau];
+            m += data[j] * data[j] + data[j + tau] * data[j + tau];
+        }
+        if m == 0.0 {
+            0.0
+        } else {
+            2.0 * r / m
+        }
+    };
+    let n: Vec<f32> = (min_tau..=max_tau).map(nsdf).collect();
+
+    let global_max = n.iter().cloned().fold(f32::MIN, f32::max);
+    let threshold = NSDF_PEAK_THRESHOLD * global_max;
+
+    // Take the first local maximum past the threshold; fall back to the global max.
+    let mut best_idx = None;
+    for i in 1..n.len().saturating_sub(1) {
+        if n[i] >= n[i - 1] && n[i] >= n[i + 1] && n[i] >= threshold {
+            best_idx = Some(i);
+            break;
+        }
+    }
+    let best_idx = best_idx.unwrap_or_else(|| {
+        n.iter()
+            .enumerate()
+            .fold((0, f32::MIN), |acc, (i, &v)| if v > acc.1 { (i, v) } else { acc })
+            .0
+    });
+
+    // Parabolic interpolation over the three NSDF samples around the peak.
+    let tau_refined = if best_idx > 0 && best_idx < n.len() - 1 {
+        let (y0, y1, y2) = (n[best_idx - 1], n[best_idx], n[best_idx + 1]);
+        let denom = y0 - 2.0 * y1 + y2;
+        if denom.abs() > f32::EPSILON {
+            (min_tau + best_idx) as f32 + 0.5 * (y0 - y2) / denom
+        } else {
+            (min_tau + best_idx) as f32
+        }
+    } else {
+        (min_tau + best_idx) as f32
+    };
+
+    Ok(smp_per_s / tau_refined)
+}
+
+/// Trims `data` to the configured loop region, as also performed (redundantly) by `load_input`.
+fn trimmed_loop<'a>(in_cfg: &cfg::Input, data: &'a [Amplitude]) -> Result<&'a [Amplitude]> {
+    let loop_begin = in_cfg.loop_begin;
+    let loop_end = in_cfg.loop_end.unwrap_or(data.len());
+    if !(loop_end > loop_begin) {
+        bail!(
+            "loop end = {} must be greater than loop begin {}",
+            loop_end,
+            loop_begin
+        );
+    }
+    Ok(&data[loop_begin..loop_end])
+}
+
+fn pitch_to_freq(pitch: Pitch, tuning: &cfg::Tuning, loop_data: &[Amplitude], smp_per_s: f32) -> Result<f32> {
+    match pitch {
+        Pitch::Hz(f) => Ok(f),
+        Pitch::Midi(midi) => Ok(midi_to_freq(tuning, midi)),
+        Pitch::Degree(degree) => Ok(tuning_to_freq(tuning, degree)),
+        Pitch::Auto => {
+            detect_pitch_nsdf(loop_data, smp_per_s, AUTO_PITCH_MIN_HZ, AUTO_PITCH_MAX_HZ)
+        }
+    }
 }
 
-fn pitch_to_freq(pitch: Pitch) -> f32 {
+/// Like `pitch_to_freq`, but for contexts without access to the raw input waveform
+/// (e.g. `SynthMode::PreserveFormants::fund_pitch`), where `Pitch::Auto` cannot be resolved.
+fn fixed_pitch_to_freq(pitch: Pitch, tuning: &cfg::Tuning) -> Result<f32> {
     match pitch {
-        Pitch::Hz(f) => f,
-        Pitch::Midi(midi) => midi_to_freq(midi),
+        Pitch::Hz(f) => Ok(f),
+        Pitch::Midi(midi) => Ok(midi_to_freq(tuning, midi)),
+        Pitch::Degree(degree) => Ok(tuning_to_freq(tuning, degree)),
+        Pitch::Auto => bail!(
+            "Pitch::Auto is only supported for cfg::Input::pitch, not SynthMode::PreserveFormants::fund_pitch"
+        ),
     }
 }
 
-fn chord_pitch_to_freq(pitch: ChordPitch, _fund_freq: Option<f32>) -> Result<f32> {
+fn chord_pitch_to_freq(
+    pitch: ChordPitch,
+    tuning: &cfg::Tuning,
+    input_fund_freq: f32,
+    fund_freq: Option<f32>,
+) -> Result<f32> {
     match pitch {
         ChordPitch::Hz(f) => Ok(f),
-        ChordPitch::Midi(midi) => Ok(midi_to_freq(midi)),
-        // TODO if ChordPitch::Harmonic, and fund_freq not present, return error.
+        ChordPitch::Midi(midi) => Ok(midi_to_freq(tuning, midi)),
+        ChordPitch::Degree(degree) => Ok(tuning_to_freq(tuning, degree)),
+        ChordPitch::Cents(cents) => Ok(input_fund_freq * cents_to_freq_mul(cents)),
+        ChordPitch::Harmonic(harmonic) => match fund_freq {
+            Some(f) => Ok(f * harmonic),
+            None => bail!(
+                "ChordPitch::Harmonic is only valid under SynthMode::PreserveFormants, which provides fund_pitch"
+            ),
+        },
     }
 }
 
@@ -86,48 +238,108 @@ struct Spectrum<T> {
     period_per_s: f32,
 }
 
-fn load_input(
-    in_cfg: &cfg::Input,
-    mut data: &[Amplitude],
-    wav_smp_per_s: u32,
-) -> Result<Spectrum<FftVec>> {
-    // Trim the wav data to the looped portion.
-    let loop_begin = in_cfg.loop_begin;
-    let loop_end = in_cfg.loop_end.unwrap_or(data.len());
-    if !(loop_end > loop_begin) {
+/// Computes a Hann window of length `n` (periodic-per-segment, used for Welch's method).
+fn hann_window(n: usize) -> RealVec {
+    use std::f32::consts::PI;
+    (0..n)
+        .map(|i| 0.5 - 0.5 * (2.0 * PI * i as f32 / n.max(1) as f32).cos())
+        .collect()
+}
+
+/// Estimates the power spectrum of `data` via Welch's method: split into overlapping,
+/// Hann-windowed segments of length `segment_len`, accumulate `|X_k|^2` across segments,
+/// then divide by the segment count and window power. Returns the square root (amplitude),
+/// with phase discarded (zeroed) since the synthesis path re-randomizes phase regardless.
+fn welch_spectrum(data: &[Amplitude], welch: cfg::Welch) -> Result<FftVec> {
+    let segment_len = welch.segment_len;
+    if segment_len < 2 || segment_len > data.len() {
         bail!(
-            "loop end = {} must be greater than loop begin {}",
-            loop_end,
-            loop_begin
+            "welch segment_len = {} must be in 2..=data.len() = {}",
+            segment_len,
+            data.len()
         );
     }
+    if !(0.0..1.0).contains(&welch.overlap) {
+        bail!("welch overlap = {} must be in [0, 1)", welch.overlap);
+    }
 
-    data = &data[loop_begin..loop_end];
+    let window = hann_window(segment_len);
+    let window_power: f32 = window.iter().map(|w| w * w).sum();
+    let hop = (((segment_len as f32) * (1.0 - welch.overlap)).round() as usize).max(1);
 
-    // Take the FFT of the looped portion.
     let mut planner = realfft::RealFftPlanner::<f32>::new();
-    let fft = planner.plan_fft_forward(data.len());
+    let fft = planner.plan_fft_forward(segment_len);
+
+    let mut power_sum = vec![0f32; segment_len / 2 + 1];
+    let mut nsegments = 0usize;
+    let mut start = 0;
+    while start + segment_len <= data.len() {
+        let mut windowed: RealVec = data[start..start + segment_len]
+            .iter()
+            .zip(&window)
+            .map(|(&x, &w)| x * w)
+            .collect();
+
+        let mut segment_spectrum = vec![FftAmplitude::zero(); segment_len / 2 + 1];
+        fft.process(&mut windowed, &mut segment_spectrum).unwrap();
+
+        for (power, bin) in power_sum.iter_mut().zip(&segment_spectrum) {
+            *power += bin.norm_sqr();
+        }
+        nsegments += 1;
+        start += hop;
+    }
 
-    let mut spectrum = {
-        let mut data_copy = Vec::from(data);
-        let mut spectrum = vec![FftAmplitude::zero(); data.len() / 2 + 1];
-        fft.process(&mut data_copy, &mut spectrum).unwrap();
-        spectrum
-    };
+    Ok(power_sum
+        .into_iter()
+        .map(|power| FftAmplitude::new((power / (nsegments as f32 * window_power)).sqrt(), 0.0))
+        .collect())
+}
 
-    // Normalize spectrum to have constant total power if input is resampled to a different length.
-    for ampl in &mut spectrum {
-        *ampl /= data.len() as f32;
-    }
+/// Returns the loaded spectrum, plus the true sample count (`smp_per_period`) it was
+/// computed from — needed by `cepstral_envelope`, which can't recover an odd sample
+/// count from the bin count alone.
+fn load_input(
+    in_cfg: &cfg::Input,
+    mut data: &[Amplitude],
+    wav_smp_per_s: u32,
+) -> Result<(Spectrum<FftVec>, usize)> {
+    // Trim the wav data to the looped portion.
+    data = trimmed_loop(in_cfg, data)?;
+
+    let (spectrum, smp_per_period) = match in_cfg.welch {
+        Some(welch) => (welch_spectrum(data, welch)?, welch.segment_len),
+        None => {
+            // Take the FFT of the looped portion.
+            let mut planner = realfft::RealFftPlanner::<f32>::new();
+            let fft = planner.plan_fft_forward(data.len());
+
+            let mut spectrum = {
+                let mut data_copy = Vec::from(data);
+                let mut spectrum = vec![FftAmplitude::zero(); data.len() / 2 + 1];
+                fft.process(&mut data_copy, &mut spectrum).unwrap();
+                spectrum
+            };
+
+            // Normalize spectrum to have constant total power if input is resampled to a different length.
+            for ampl in &mut spectrum {
+                *ampl /= data.len() as f32;
+            }
+
+            (spectrum, data.len())
+        }
+    };
 
     let mut smp_per_s = in_cfg.transpose.sample_rate.unwrap_or(wav_smp_per_s) as f32;
     smp_per_s *= cents_to_freq_mul(in_cfg.transpose.detune_cents);
-    let smp_per_period = data.len() as f32;
 
-    Ok(Spectrum {
-        spectrum,
-        period_per_s: smp_per_s / smp_per_period,
-    })
+    Ok((
+        Spectrum {
+            spectrum,
+            period_per_s: smp_per_s / smp_per_period as f32,
+        },
+        smp_per_period,
+    ))
 }
 
 struct SpectrumAndNote<T> {
@@ -312,13 +524,251 @@ fn note_to_harmonics(input_note: &SpectrumAndNote<&FftSlice>) -> Vec<Amplitude>
     output
 }
 
-fn synthesize(out_cfg: &cfg::Output, input_note: SpectrumAndNote<&FftSlice>) -> Result<RealVec> {
+/// Reads `spectrum[i]`, treating out-of-range indices as zero (the spectrum tapers to
+/// silence past its edges rather than wrapping or erroring).
+fn spectrum_at(spectrum: &[Amplitude], i: isize) -> Amplitude {
+    if i < 0 || i as usize >= spectrum.len() {
+        0.0
+    } else {
+        spectrum[i as usize]
+    }
+}
+
+const POLYPHASE_TAPS: usize = 16;
+const POLYPHASE_SUBPHASES: usize = 32;
+
+/// A windowed-sinc FIR table for polyphase resampling: `POLYPHASE_SUBPHASES` sub-sample
+/// phases, each holding `POLYPHASE_TAPS` Blackman-windowed sinc coefficients.
+fn polyphase_table() -> Vec<[f32; POLYPHASE_TAPS]> {
+    use std::f32::consts::PI;
+    let half = POLYPHASE_TAPS as isize / 2;
+    (0..POLYPHASE_SUBPHASES)
+        .map(|subphase| {
+            let frac = subphase as f32 / POLYPHASE_SUBPHASES as f32;
+            let mut taps = [0f32; POLYPHASE_TAPS];
+            for (k, tap) in taps.iter_mut().enumerate() {
+                let x = (k as isize - half) as f32 - frac;
+                let sinc = if x.abs() < 1e-6 {
+                    1.0
+                } else {
+                    (PI * x).sin() / (PI * x)
+                };
+                let blackman = 0.42 - 0.5 * (2.0 * PI * k as f32 / (POLYPHASE_TAPS - 1) as f32).cos()
+                    + 0.08 * (4.0 * PI * k as f32 / (POLYPHASE_TAPS - 1) as f32).cos();
+                *tap = sinc * blackman;
+            }
+            taps
+        })
+        .collect()
+}
+
+/// Resamples `spectrum` at fractional bin index `idx`, per `mode`.
+/// `polyphase_table` is only read when `mode == Polyphase` (pass `&[]` otherwise).
+fn resample_spectrum(
+    spectrum: &[Amplitude],
+    idx: f32,
+    mode: cfg::InterpolationMode,
+    polyphase_table: &[[f32; POLYPHASE_TAPS]],
+) -> Amplitude {
+    use cfg::InterpolationMode;
+    match mode {
+        InterpolationMode::Nearest => spectrum_at(spectrum, idx.round() as isize),
+        InterpolationMode::Linear => {
+            let i0 = idx.floor() as isize;
+            let mu = idx - i0 as f32;
+            spectrum_at(spectrum, i0) * (1.0 - mu) + spectrum_at(spectrum, i0 + 1) * mu
+        }
+        InterpolationMode::Cosine => {
+            let i0 = idx.floor() as isize;
+            let mu = idx - i0 as f32;
+            let mu2 = (1.0 - (mu * std::f32::consts::PI).cos()) / 2.0;
+            spectrum_at(spectrum, i0) * (1.0 - mu2) + spectrum_at(spectrum, i0 + 1) * mu2
+        }
+        InterpolationMode::Cubic => {
+            // 4-point Catmull-Rom kernel over bins [i-1, i, i+1, i+2].
+            let i1 = idx.floor() as isize;
+            let mu = idx - i1 as f32;
+            let (p0, p1, p2, p3) = (
+                spectrum_at(spectrum, i1 - 1),
+                spectrum_at(spectrum, i1),
+                spectrum_at(spectrum, i1 + 1),
+                spectrum_at(spectrum, i1 + 2),
+            );
+            let a0 = -0.5 * p0 + 1.5 * p1 - 1.5 * p2 + 0.5 * p3;
+            let a1 = p0 - 2.5 * p1 + 2.0 * p2 - 0.5 * p3;
+            let a2 = -0.5 * p0 + 0.5 * p2;
+            let a3 = p1;
+            ((a0 * mu + a1) * mu + a2) * mu + a3
+        }
+        InterpolationMode::Polyphase => {
+            let i0 = idx.floor() as isize;
+            let mu = idx - i0 as f32;
+            // Round up to subphase 0 of the next bin, rather than wrapping back to
+            // subphase 0 of this bin (which would jump the filter by a whole sample).
+            let subphase_f = (mu * POLYPHASE_SUBPHASES as f32).round() as usize;
+            let (i0, subphase) = if subphase_f == POLYPHASE_SUBPHASES {
+                (i0 + 1, 0)
+            } else {
+                (i0, subphase_f)
+            };
+            let taps = &polyphase_table[subphase];
+            let half = POLYPHASE_TAPS as isize / 2;
+            taps.iter()
+                .enumerate()
+                .map(|(k, &tap)| tap * spectrum_at(spectrum, i0 + k as isize - half))
+                .sum()
+        }
+    }
+}
+
+/// Extracts a smooth spectral envelope `E(f)` from `spectrum` via cepstral liftering:
+/// take the log magnitude, inverse-FFT to the cepstrum, zero out all but the low
+/// quefrencies (those within `lifter_order` bins of q=0, counting the mirrored
+/// negative quefrencies), FFT back, and exponentiate. The result is
+/// sampled in the same "cyc/period" bin units as `spectrum`.
+///
+/// Only used when mode is SynthMode::PreserveFormants.
+///
+/// `nsamp` is the true sample count `spectrum` was computed from (`spectrum.len()` alone
+/// can't recover it: an odd `nsamp` produces the same `nsamp/2+1` bin count as `nsamp-1`).
+fn cepstral_envelope(spectrum: &FftSlice, nsamp: usize, lifter_order: usize) -> RealVec {
+    const MIN_MAGNITUDE: f32 = 1e-9;
+
+    let mut log_magnitude: FftVec = spectrum
+        .iter()
+        .map(|c| FftAmplitude::new(c.norm().max(MIN_MAGNITUDE).ln(), 0.0))
+        .collect();
+    // Nyquist must be purely real for the inverse real FFT, same as `synthesize`'s output.
+    if let Some(nyquist) = log_magnitude.last_mut() {
+        *nyquist = FftAmplitude::new(nyquist.re, 0.0);
+    }
+
+    let mut planner = realfft::RealFftPlanner::<f32>::new();
+
+    let mut cepstrum = vec![0f32; nsamp];
+    planner
+        .plan_fft_inverse(nsamp)
+        .process(&mut log_magnitude, &mut cepstrum)
+        .unwrap();
+    for c in &mut cepstrum {
+        *c /= nsamp as f32;
+    }
+
+    // Lifter: keep only the low quefrencies (and their mirror image) around q=0.
+    for (q, c) in cepstrum.iter_mut().enumerate() {
+        if q.min(nsamp - q) > lifter_order {
+            *c = 0.0;
+        }
+    }
+
+    let mut log_envelope = vec![FftAmplitude::zero(); nsamp / 2 + 1];
+    planner
+        .plan_fft_forward(nsamp)
+        .process(&mut cepstrum, &mut log_envelope)
+        .unwrap();
+
+    log_envelope.iter().map(|c| c.re.exp()).collect()
+}
+
+/// Like `add_note_direct`, but instead of bucketing input power into integer harmonics,
+/// resamples the continuous input magnitude spectrum at each output harmonic frequency.
+/// Only used when mode is SynthMode::PreserveSpectrum.
+fn add_note_preserve_spectrum(
+    input_note: &SpectrumAndNote<&FftSlice>,
+    output_note: &mut SpectrumAndNote<&mut FftSlice>,
+    interpolation: cfg::InterpolationMode,
+    stdev_rel: f32,
+    volume: f32,
+    random_amplitudes: bool,
+    rng: &mut Random,
+) {
+    let input_magnitudes: Vec<Amplitude> = input_note.spectrum.iter().map(|c| c.norm()).collect();
+    let polyphase_table = match interpolation {
+        cfg::InterpolationMode::Polyphase => polyphase_table(),
+        _ => Vec::new(),
+    };
+
+    for harmonic in 1.. {
+        let cyc_per_s = output_note.cyc_per_s * harmonic as f32;
+        let idx = cyc_per_s / input_note.period_per_s;
+        let amplitude = resample_spectrum(&input_magnitudes, idx, interpolation, &polyphase_table);
+
+        if add_harmonic(
+            &mut Spectrum {
+                spectrum: /*mut*/ output_note.spectrum,
+                period_per_s: output_note.period_per_s,
+            },
+            cyc_per_s,
+            stdev_rel,
+            volume * amplitude,
+            random_amplitudes,
+            /*mut*/ rng,
+        )
+        .is_err()
+        {
+            break;
+        }
+    }
+}
+
+/// Like `add_note_direct`, but samples each output harmonic's amplitude from `envelope`
+/// (evaluated at the harmonic series of `output_note`'s own pitch, i.e. the actual absolute
+/// frequency each harmonic is rendered at) instead of the input's own harmonics, so formants
+/// stay fixed in absolute Hz regardless of `output_note`'s pitch or transposition.
+/// Only used when mode is SynthMode::PreserveFormants.
+fn add_note_preserve_formants(
+    envelope: &RealVec,
+    envelope_period_per_s: f32,
+    output_note: &mut SpectrumAndNote<&mut FftSlice>,
+    stdev_rel: f32,
+    volume: f32,
+    random_amplitudes: bool,
+    rng: &mut Random,
+) {
+    for harmonic in 1.. {
+        let idx = output_note.cyc_per_s * harmonic as f32 / envelope_period_per_s;
+        let amplitude = resample_spectrum(envelope, idx, cfg::InterpolationMode::Linear, &[]);
+
+        if add_harmonic(
+            &mut Spectrum {
+                spectrum: /*mut*/ output_note.spectrum,
+                period_per_s: output_note.period_per_s,
+            },
+            output_note.cyc_per_s * harmonic as f32,
+            stdev_rel,
+            volume * amplitude,
+            random_amplitudes,
+            /*mut*/ rng,
+        )
+        .is_err()
+        {
+            break;
+        }
+    }
+}
+
+fn synthesize(
+    out_cfg: &cfg::Output,
+    tuning: &cfg::Tuning,
+    transpose_ratio: f32,
+    input_nsamp: usize,
+    input_note: SpectrumAndNote<&FftSlice>,
+) -> Result<RealVec> {
     use realfft::num_complex::ComplexFloat;
 
     // Setup state based on out_cfg.
     let out_nsamp = duration_to_samples(out_cfg.duration, out_cfg.sample_rate);
     let fund_freq: Option<f32> = match out_cfg.mode {
-        // TODO if PreserveFormants(fund_pitch), return Some(pitch_to_freq(fund_pitch)).
+        cfg::SynthMode::PreserveFormants { fund_pitch, .. } => {
+            Some(fixed_pitch_to_freq(fund_pitch, tuning)?)
+        }
+        _ => None,
+    };
+    // Only computed once; cheap relative to per-note work, and shared across every chord note.
+    let envelope: Option<RealVec> = match out_cfg.mode {
+        cfg::SynthMode::PreserveFormants { lifter_order, .. } => {
+            Some(cepstral_envelope(input_note.spectrum, input_nsamp, lifter_order))
+        }
         _ => None,
     };
     let master_volume = volume_to_ampl(out_cfg.master_volume);
@@ -337,7 +787,8 @@ fn synthesize(out_cfg: &cfg::Output, input_note: SpectrumAndNote<&FftSlice>) ->
 
     // Fill spectrum with each note.
     for note in &out_cfg.chord {
-        let cyc_per_s = chord_pitch_to_freq(note.pitch, fund_freq)?;
+        let cyc_per_s = chord_pitch_to_freq(note.pitch, tuning, input_note.cyc_per_s, fund_freq)?
+            * transpose_ratio;
         let volume = master_volume * volume_to_ampl(note.volume);
 
         use cfg::SynthMode;
@@ -357,6 +808,40 @@ fn synthesize(out_cfg: &cfg::Output, input_note: SpectrumAndNote<&FftSlice>) ->
                     &mut rng,
                 );
             }
+            SynthMode::PreserveSpectrum { stdev, interpolation } => {
+                add_note_preserve_spectrum(
+                    &input_note,
+                    &mut SpectrumAndNote {
+                        spectrum: &mut out_spectrum,
+                        period_per_s: out_smp_per_s / out_smp_per_period,
+                        cyc_per_s,
+                    },
+                    interpolation,
+                    stdev,
+                    volume,
+                    random_amplitudes,
+                    &mut rng,
+                );
+            }
+            SynthMode::PreserveFormants { stdev, .. } => {
+                let envelope = envelope
+                    .as_ref()
+                    .expect("envelope is computed above whenever mode is PreserveFormants");
+
+                add_note_preserve_formants(
+                    envelope,
+                    input_note.period_per_s,
+                    &mut SpectrumAndNote {
+                        spectrum: &mut out_spectrum,
+                        period_per_s: out_smp_per_s / out_smp_per_period,
+                        cyc_per_s,
+                    },
+                    stdev,
+                    volume,
+                    random_amplitudes,
+                    &mut rng,
+                );
+            }
         }
     }
 
@@ -378,13 +863,28 @@ fn synthesize(out_cfg: &cfg::Output, input_note: SpectrumAndNote<&FftSlice>) ->
 /// but not yet trimmed to the looped section only.
 ///
 pub fn process(cfg: &Config, data: &[Amplitude], wav_smp_per_s: u32) -> Result<RealVec> {
+    process_transposed(cfg, data, wav_smp_per_s, 1.0)
+}
+
+/// Like `process`, but multiplies every rendered frequency by `transpose_ratio`.
+/// Used by the multisample exporter to render the same patch at different zone roots.
+pub fn process_transposed(
+    cfg: &Config,
+    data: &[Amplitude],
+    wav_smp_per_s: u32,
+    transpose_ratio: f32,
+) -> Result<RealVec> {
     let in_cfg = &cfg.input;
 
-    let spectrum = load_input(&cfg.input, data, wav_smp_per_s)?;
-    let freq = pitch_to_freq(in_cfg.pitch);
+    let (spectrum, input_nsamp) = load_input(&cfg.input, data, wav_smp_per_s)?;
+    let loop_data = trimmed_loop(in_cfg, data)?;
+    let freq = pitch_to_freq(in_cfg.pitch, &cfg.tuning, loop_data, wav_smp_per_s as f32)?;
 
     let out = synthesize(
         &cfg.output,
+        &cfg.tuning,
+        transpose_ratio,
+        input_nsamp,
         SpectrumAndNote {
             spectrum: &spectrum.spectrum,
             period_per_s: spectrum.period_per_s,
@@ -393,3 +893,38 @@ pub fn process(cfg: &Config, data: &[Amplitude], wav_smp_per_s: u32) -> Result<R
     )?;
     Ok(out)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `tuning_to_freq` must resolve `reference_degree` to exactly `reference_freq`,
+    /// for every `TuningSystem` variant.
+    #[test]
+    fn tuning_to_freq_reference_degree_is_reference_freq() {
+        let equal_temperament = cfg::Tuning {
+            reference_degree: 69,
+            reference_freq: 440.0,
+            system: cfg::TuningSystem::EqualTemperament {
+                divisions_per_octave: 12,
+            },
+        };
+        assert_eq!(
+            tuning_to_freq(&equal_temperament, equal_temperament.reference_degree),
+            equal_temperament.reference_freq
+        );
+
+        let scala = cfg::Tuning {
+            reference_degree: 69,
+            reference_freq: 440.0,
+            system: cfg::TuningSystem::Scala {
+                cents_per_step: vec![100.0, 200.0, 300.0, 400.0, 500.0, 600.0, 700.0, 800.0, 900.0, 1000.0, 1100.0, 1200.0],
+                period_cents: 1200.0,
+            },
+        };
+        assert_eq!(
+            tuning_to_freq(&scala, scala.reference_degree),
+            scala.reference_freq
+        );
+    }
+}
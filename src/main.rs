@@ -19,7 +19,9 @@ struct Opt {
     #[structopt(name = "CONFIG", parse(from_os_str))]
     cfg: PathBuf,
 
-    /// Output file to write to
+    /// Output file to write to.
+    /// If the config has a `multisample` section, this is instead the `.sfz` path
+    /// written alongside the per-zone WAVs.
     #[structopt(name = "OUT_WAV", parse(from_os_str))]
     out_wav: PathBuf,
 }
@@ -32,6 +34,8 @@ mod common {
 
 mod dsp;
 
+mod multisample;
+
 use common::Amplitude;
 
 fn wav_to_float(wav: BitDepth) -> Vec<Amplitude> {
@@ -59,7 +63,7 @@ fn wav_to_float(wav: BitDepth) -> Vec<Amplitude> {
     }
 }
 
-fn float_to_i16(data: &[Amplitude]) -> Result<Vec<i16>> {
+pub(crate) fn float_to_i16(data: &[Amplitude]) -> Result<Vec<i16>> {
     let mut out = vec![0i16; data.len()];
     for (f, i) in data.iter().zip(&mut out) {
         let f = (f * (1 << 15) as f32).round();
@@ -125,6 +129,10 @@ fn main() -> Result<()> {
     };
     cfg.validate()?;
 
+    if let Some(multisample) = &cfg.multisample {
+        return multisample::export(&cfg, multisample, &data, header.sampling_rate, &opt.out_wav);
+    }
+
     let out_data = dsp::process(&cfg, &data, header.sampling_rate)?;
 
     let out_wav_data = float_to_i16(&out_data)?;
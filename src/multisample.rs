@@ -0,0 +1,112 @@
+//! Exports a patch as a multisampled, loopable SFZ instrument. Each padsynth output
+//! buffer is exactly periodic over `out_nsamp` samples, so every rendered zone is
+//! seamlessly loopable end-to-end.
+
+use anyhow::{Context, Result};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use crate::cfg::{Config, Multisample};
+use crate::common::Amplitude;
+use crate::{dsp, float_to_i16};
+
+struct Zone {
+    note: i32,
+    lokey: i32,
+    hikey: i32,
+    wav_filename: String,
+    out_nsamp: usize,
+}
+
+/// Renders `cfg`'s patch at every zone root in `multisample`, writing one WAV per zone
+/// next to `sfz_path` plus an `.sfz` file (written at `sfz_path`) mapping each sample
+/// to its key range, root key, and a continuous sustain loop over the whole buffer.
+pub fn export(
+    cfg: &Config,
+    multisample: &Multisample,
+    data: &[Amplitude],
+    wav_smp_per_s: u32,
+    sfz_path: &Path,
+) -> Result<()> {
+    let stem = sfz_path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "instrument".to_string());
+    let out_dir = sfz_path.parent().unwrap_or_else(|| Path::new("."));
+
+    let notes: Vec<i32> = (multisample.low..=multisample.high)
+        .step_by(multisample.step as usize)
+        .collect();
+
+    let mut zones = Vec::with_capacity(notes.len());
+    for (i, &note) in notes.iter().enumerate() {
+        let lokey = if i == 0 {
+            0
+        } else {
+            note - multisample.step / 2
+        };
+        let hikey = if i + 1 == notes.len() {
+            127
+        } else {
+            notes[i + 1] - multisample.step / 2 - 1
+        };
+
+        let transpose_ratio = dsp::tuning_to_freq(&cfg.tuning, note)
+            / dsp::tuning_to_freq(&cfg.tuning, multisample.root_note);
+        let out_data = dsp::process_transposed(cfg, data, wav_smp_per_s, transpose_ratio)
+            .with_context(|| format!("rendering multisample zone for MIDI note {}", note))?;
+        let out_nsamp = out_data.len();
+        let out_wav_data = float_to_i16(&out_data)
+            .with_context(|| format!("converting multisample zone for MIDI note {} to i16", note))?;
+
+        let wav_filename = format!("{}_note{}.wav", stem, note);
+        let wav_path = out_dir.join(&wav_filename);
+        let out_file = File::create(&wav_path)
+            .with_context(|| format!("creating WAV file '{}'", wav_path.display()))?;
+        let mut buf_writer = BufWriter::new(out_file);
+        wav::write(
+            wav::Header::new(1, 1, cfg.output.sample_rate, 16),
+            &wav::BitDepth::Sixteen(out_wav_data),
+            &mut buf_writer,
+        )
+        .with_context(|| format!("writing WAV file '{}'", wav_path.display()))?;
+        buf_writer
+            .flush()
+            .with_context(|| format!("flushing WAV file '{}'", wav_path.display()))?;
+
+        zones.push(Zone {
+            note,
+            lokey,
+            hikey,
+            wav_filename,
+            out_nsamp,
+        });
+    }
+
+    write_sfz(sfz_path, &zones)
+}
+
+fn write_sfz(sfz_path: &Path, zones: &[Zone]) -> Result<()> {
+    let out_file = File::create(sfz_path)
+        .with_context(|| format!("creating SFZ file '{}'", sfz_path.display()))?;
+    let mut buf_writer = BufWriter::new(out_file);
+
+    for zone in zones {
+        writeln!(buf_writer, "<region>")?;
+        writeln!(buf_writer, "sample={}", zone.wav_filename)?;
+        writeln!(buf_writer, "lokey={}", zone.lokey)?;
+        writeln!(buf_writer, "hikey={}", zone.hikey)?;
+        writeln!(buf_writer, "pitch_keycenter={}", zone.note)?;
+        writeln!(buf_writer, "loop_mode=loop_continuous")?;
+        writeln!(buf_writer, "loop_start=0")?;
+        // Safe because the synthesized buffer is exactly periodic over its whole duration.
+        writeln!(buf_writer, "loop_end={}", zone.out_nsamp - 1)?;
+        writeln!(buf_writer)?;
+    }
+
+    buf_writer
+        .flush()
+        .with_context(|| format!("flushing SFZ file '{}'", sfz_path.display()))?;
+    Ok(())
+}